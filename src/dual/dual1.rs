@@ -209,11 +209,24 @@ impl Dual {
         }
     }
 
-    fn __pow__(&self, power: f64, modulo: Option<i32>) -> Self {
+    fn __pow__(&self, power: DualOrF64, modulo: Option<i32>) -> Self {
         if modulo.unwrap_or(0) != 0 {
             panic!("Power function with mod not available for Dual.")
         }
-        self.clone().pow(power)
+        match power {
+            DualOrF64::Dual(d) => self.clone().pow(d),
+            DualOrF64::F64(f) => self.clone().pow(f),
+        }
+    }
+
+    fn __rpow__(&self, other: DualOrF64, modulo: Option<i32>) -> Self {
+        if modulo.unwrap_or(0) != 0 {
+            panic!("Power function with mod not available for Dual.")
+        }
+        match other {
+            DualOrF64::Dual(d) => d.pow(self.clone()),
+            DualOrF64::F64(f) => Dual::new(f, Vec::new(), Vec::new()).pow(self.clone()),
+        }
     }
 
     fn __exp__(&self) -> Self {
@@ -224,8 +237,63 @@ impl Dual {
         self.abs()
     }
 
-    fn __log__(&self) -> Self {
-        self.log()
+    fn __log__(&self, base: Option<f64>) -> Self {
+        match base {
+            Some(b) => self.log(b),
+            None => self.ln(),
+        }
+    }
+
+    fn __sqrt__(&self) -> Self {
+        self.sqrt()
+    }
+
+    fn __sin__(&self) -> Self {
+        self.sin()
+    }
+
+    fn __cos__(&self) -> Self {
+        self.cos()
+    }
+
+    fn __tan__(&self) -> Self {
+        self.tan()
+    }
+
+    fn __asin__(&self) -> Self {
+        self.asin()
+    }
+
+    fn __acos__(&self) -> Self {
+        self.acos()
+    }
+
+    fn __atan__(&self) -> Self {
+        self.atan()
+    }
+
+    fn __sinh__(&self) -> Self {
+        self.sinh()
+    }
+
+    fn __cosh__(&self) -> Self {
+        self.cosh()
+    }
+
+    fn __tanh__(&self) -> Self {
+        self.tanh()
+    }
+
+    fn __powi__(&self, n: i32) -> Self {
+        self.clone().powi(n)
+    }
+
+    fn __exp2__(&self) -> Self {
+        self.exp2()
+    }
+
+    fn __cbrt__(&self) -> Self {
+        self.cbrt()
     }
 
     fn __float__(&self) -> f64 {
@@ -323,13 +391,155 @@ impl Dual {
         }
     }
 
-    pub fn log(&self) -> Self {
+    pub fn inv(&self) -> Self {
+        Dual {
+            real: self.real.recip(),
+            vars: Arc::clone(&self.vars),
+            dual: (-1.0 / (self.real * self.real)) * &self.dual,
+        }
+    }
+
+    pub fn ln(&self) -> Self {
+        if self.real <= 0.0 {
+            panic!("`ln` is undefined for a Dual with `real` <= 0.0.")
+        }
         Dual {
             real: self.real.ln(),
             vars: Arc::clone(&self.vars),
             dual: (1.0 / self.real) * &self.dual,
         }
     }
+
+    pub fn log(&self, base: f64) -> Self {
+        if self.real <= 0.0 {
+            panic!("`log` is undefined for a Dual with `real` <= 0.0.")
+        }
+        let base_ln = base.ln();
+        Dual {
+            real: self.real.log(base),
+            vars: Arc::clone(&self.vars),
+            dual: (1.0 / (self.real * base_ln)) * &self.dual,
+        }
+    }
+
+    pub fn sqrt(&self) -> Self {
+        if self.real <= 0.0 {
+            panic!("`sqrt` is undefined for a Dual with `real` <= 0.0.")
+        }
+        let c = self.real.sqrt();
+        Dual {
+            real: c,
+            vars: Arc::clone(&self.vars),
+            dual: (0.5 / c) * &self.dual,
+        }
+    }
+
+    pub fn cbrt(&self) -> Self {
+        let c = self.real.cbrt();
+        Dual {
+            real: c,
+            vars: Arc::clone(&self.vars),
+            dual: (1.0 / (3.0 * c * c)) * &self.dual,
+        }
+    }
+
+    pub fn exp2(&self) -> Self {
+        let c = self.real.exp2();
+        Dual {
+            real: c,
+            vars: Arc::clone(&self.vars),
+            dual: (c * std::f64::consts::LN_2) * &self.dual,
+        }
+    }
+
+    pub fn sin(&self) -> Self {
+        Dual {
+            real: self.real.sin(),
+            vars: Arc::clone(&self.vars),
+            dual: self.real.cos() * &self.dual,
+        }
+    }
+
+    pub fn cos(&self) -> Self {
+        Dual {
+            real: self.real.cos(),
+            vars: Arc::clone(&self.vars),
+            dual: -self.real.sin() * &self.dual,
+        }
+    }
+
+    pub fn tan(&self) -> Self {
+        let c = self.real.tan();
+        Dual {
+            real: c,
+            vars: Arc::clone(&self.vars),
+            dual: (1.0 + c * c) * &self.dual,
+        }
+    }
+
+    pub fn asin(&self) -> Self {
+        if self.real.abs() > 1.0 {
+            panic!("`asin` is undefined for a Dual with `real` outside [-1.0, 1.0].")
+        }
+        Dual {
+            real: self.real.asin(),
+            vars: Arc::clone(&self.vars),
+            dual: (1.0 / (1.0 - self.real * self.real).sqrt()) * &self.dual,
+        }
+    }
+
+    pub fn acos(&self) -> Self {
+        if self.real.abs() > 1.0 {
+            panic!("`acos` is undefined for a Dual with `real` outside [-1.0, 1.0].")
+        }
+        Dual {
+            real: self.real.acos(),
+            vars: Arc::clone(&self.vars),
+            dual: (-1.0 / (1.0 - self.real * self.real).sqrt()) * &self.dual,
+        }
+    }
+
+    pub fn atan(&self) -> Self {
+        Dual {
+            real: self.real.atan(),
+            vars: Arc::clone(&self.vars),
+            dual: (1.0 / (1.0 + self.real * self.real)) * &self.dual,
+        }
+    }
+
+    pub fn sinh(&self) -> Self {
+        Dual {
+            real: self.real.sinh(),
+            vars: Arc::clone(&self.vars),
+            dual: self.real.cosh() * &self.dual,
+        }
+    }
+
+    pub fn cosh(&self) -> Self {
+        Dual {
+            real: self.real.cosh(),
+            vars: Arc::clone(&self.vars),
+            dual: self.real.sinh() * &self.dual,
+        }
+    }
+
+    pub fn tanh(&self) -> Self {
+        let c = self.real.tanh();
+        Dual {
+            real: c,
+            vars: Arc::clone(&self.vars),
+            dual: (1.0 - c * c) * &self.dual,
+        }
+    }
+
+    pub fn powi(self, n: i32) -> Self {
+        let f1 = n as f64 * self.real.powi(n - 1);
+        Dual {
+            real: self.real.powi(n),
+            dual: self.dual * f1,
+            vars: self.vars,
+        }
+    }
 }
 
 impl num_traits::identities::One for Dual {
@@ -359,6 +569,22 @@ impl num_traits::Pow<f64> for Dual {
     }
 }
 
+impl num_traits::Pow<Dual> for Dual {
+    type Output = Dual;
+    fn pow(self, power: Dual) -> Dual {
+        if self.real <= 0.0 {
+            panic!("Dual raised to a Dual power requires a positive base so that `ln(real)` is defined.")
+        }
+        let (x, y) = self.to_combined_vars(&power);
+        let z_real = x.real.powf(y.real);
+        return Dual {
+            dual: z_real * (y.real / x.real * &x.dual + x.real.ln() * &y.dual),
+            real: z_real,
+            vars: Arc::clone(&x.vars),
+        }
+    }
+}
+
 impl std::ops::AddAssign for Dual {
     fn add_assign(&mut self, other: Self) {
         let z = self.clone() + other;
@@ -415,8 +641,8 @@ impl_op_ex!(* |a: &Dual, b: &Dual| -> Dual {
 });
 
 impl_op_ex!(/ |a: &Dual, b: f64| -> Dual { Dual {vars: Arc::clone(&a.vars), real: a.real / b, dual: &a.dual / b} });
-impl_op_ex!(/ |a: f64, b: &Dual| -> Dual { a * b.clone().pow(-1.0) });
-impl_op_ex!(/ |a: &Dual, b: &Dual| -> Dual { a * b.clone().pow(-1.0) });
+impl_op_ex!(/ |a: f64, b: &Dual| -> Dual { a * b.inv() });
+impl_op_ex!(/ |a: &Dual, b: &Dual| -> Dual { a * b.inv() });
 
 impl PartialEq<f64> for Dual {
     fn eq(&self, other: &f64) -> bool {
@@ -487,8 +713,168 @@ impl std::iter::Sum for Dual {
     }
 }
 
-pub fn arr1_dot(a1: Array1<Dual>, a2: Array1<Dual>) -> Dual {
-    // Consumes two one dimensional arrays and produces a scalar value of their dot product.
-    let z = a1.into_iter().zip(a2.into_iter()).map(|(x, y)| x * y).collect::<Vec<Dual>>();
-    return z.into_iter().sum::<Dual>()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pow_dual_exponent_matches_analytic_gradient() {
+        let x = Dual::new(2.0, vec!["x".to_string()], vec![]);
+        let y = Dual::new(3.0, vec!["y".to_string()], vec![]);
+        let z = x.pow(y);
+
+        assert!((z.real - 8.0).abs() < 1e-12); // 2**3
+
+        let vars = vec!["x".to_string(), "y".to_string()];
+        let grad = z.ggradient(vars);
+        // d(x**y)/dx = y * x**(y-1)
+        assert!((grad[[0]] - 12.0).abs() < 1e-9);
+        // d(x**y)/dy = x**y * ln(x)
+        assert!((grad[[1]] - 8.0 * 2.0_f64.ln()).abs() < 1e-9);
+    }
+
+    fn dual_x(real: f64) -> Dual {
+        Dual::new(real, vec!["x".to_string()], vec![1.0])
+    }
+
+    fn d_dx(d: &Dual) -> f64 {
+        d.ggradient(vec!["x".to_string()])[[0]]
+    }
+
+    #[test]
+    fn sin_matches_analytic_gradient() {
+        let z = dual_x(0.5).sin();
+        assert!((z.real - 0.5_f64.sin()).abs() < 1e-12);
+        assert!((d_dx(&z) - 0.5_f64.cos()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cos_matches_analytic_gradient() {
+        let z = dual_x(0.5).cos();
+        assert!((z.real - 0.5_f64.cos()).abs() < 1e-12);
+        assert!((d_dx(&z) - (-0.5_f64.sin())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tan_matches_analytic_gradient() {
+        let z = dual_x(0.5).tan();
+        assert!((z.real - 0.5_f64.tan()).abs() < 1e-12);
+        let c = 0.5_f64.tan();
+        assert!((d_dx(&z) - (1.0 + c * c)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn asin_matches_analytic_gradient() {
+        let z = dual_x(0.5).asin();
+        assert!((z.real - 0.5_f64.asin()).abs() < 1e-12);
+        assert!((d_dx(&z) - 1.0 / (1.0_f64 - 0.25).sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn asin_panics_outside_domain() {
+        dual_x(1.5).asin();
+    }
+
+    #[test]
+    fn acos_matches_analytic_gradient() {
+        let z = dual_x(0.5).acos();
+        assert!((z.real - 0.5_f64.acos()).abs() < 1e-12);
+        assert!((d_dx(&z) - (-1.0 / (1.0_f64 - 0.25).sqrt())).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn acos_panics_outside_domain() {
+        dual_x(-1.5).acos();
+    }
+
+    #[test]
+    fn atan_matches_analytic_gradient() {
+        let z = dual_x(0.5).atan();
+        assert!((z.real - 0.5_f64.atan()).abs() < 1e-12);
+        assert!((d_dx(&z) - 1.0 / (1.0_f64 + 0.25)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sinh_matches_analytic_gradient() {
+        let z = dual_x(0.5).sinh();
+        assert!((z.real - 0.5_f64.sinh()).abs() < 1e-12);
+        assert!((d_dx(&z) - 0.5_f64.cosh()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cosh_matches_analytic_gradient() {
+        let z = dual_x(0.5).cosh();
+        assert!((z.real - 0.5_f64.cosh()).abs() < 1e-12);
+        assert!((d_dx(&z) - 0.5_f64.sinh()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tanh_matches_analytic_gradient() {
+        let z = dual_x(0.5).tanh();
+        assert!((z.real - 0.5_f64.tanh()).abs() < 1e-12);
+        let c = 0.5_f64.tanh();
+        assert!((d_dx(&z) - (1.0 - c * c)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sqrt_matches_analytic_gradient() {
+        let z = dual_x(4.0).sqrt();
+        assert!((z.real - 2.0).abs() < 1e-12);
+        assert!((d_dx(&z) - 0.25).abs() < 1e-9); // 0.5 / sqrt(4)
+    }
+
+    #[test]
+    #[should_panic]
+    fn sqrt_panics_for_non_positive_real() {
+        dual_x(0.0).sqrt();
+    }
+
+    #[test]
+    fn cbrt_matches_analytic_gradient() {
+        let z = dual_x(8.0).cbrt();
+        assert!((z.real - 2.0).abs() < 1e-12);
+        assert!((d_dx(&z) - 1.0 / (3.0 * 4.0)).abs() < 1e-9); // 1 / (3 * cbrt(8)^2)
+    }
+
+    #[test]
+    fn exp2_matches_analytic_gradient() {
+        let z = dual_x(3.0).exp2();
+        assert!((z.real - 8.0).abs() < 1e-12);
+        assert!((d_dx(&z) - 8.0 * std::f64::consts::LN_2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn powi_matches_analytic_gradient() {
+        let z = dual_x(2.0).powi(3);
+        assert!((z.real - 8.0).abs() < 1e-12);
+        assert!((d_dx(&z) - 12.0).abs() < 1e-9); // 3 * 2^2
+    }
+
+    #[test]
+    fn ln_matches_analytic_gradient() {
+        let z = dual_x(2.0).ln();
+        assert!((z.real - 2.0_f64.ln()).abs() < 1e-12);
+        assert!((d_dx(&z) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ln_panics_for_non_positive_real() {
+        dual_x(0.0).ln();
+    }
+
+    #[test]
+    fn log_matches_analytic_gradient() {
+        let z = dual_x(8.0).log(2.0);
+        assert!((z.real - 3.0).abs() < 1e-12);
+        assert!((d_dx(&z) - 1.0 / (8.0 * 2.0_f64.ln())).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn log_panics_for_non_positive_real() {
+        dual_x(0.0).log(2.0);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,562 @@
+use numpy::{ToPyArray, PyArray1, PyArray2};
+use ndarray::{Array1, Array2, Array};
+use num_traits;
+use num_traits::Pow;
+use std::sync::Arc;
+use indexmap::set::IndexSet;
+use std::cmp::Ordering;
+use auto_ops::{impl_op, impl_op_ex, impl_op_ex_commutative};
+
+use pyo3::prelude::*;
+use pyo3::conversion::FromPyObject;
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct Dual2 {
+    pub real : f64,
+    pub vars : Arc<IndexSet<String>>,
+    pub dual : Array1<f64>,
+    pub dual2 : Array2<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd, FromPyObject)]
+pub enum Dual2OrF64 {
+    Dual2(Dual2),
+    F64(f64),
+}
+
+impl Dual2OrF64 {
+    pub fn abs(&self) -> Self {
+        match self {
+            Dual2OrF64::Dual2(d) => Dual2OrF64::F64(d.abs()),
+            Dual2OrF64::F64(f) => Dual2OrF64::F64(f.abs()),
+        }
+    }
+}
+
+
+#[pymethods]
+impl Dual2 {
+    /// Return a Dual2 with associated metrics, carrying gradient and Hessian information.
+    ///
+    /// # Arguments
+    ///
+    /// * `real` - An f64 holding the representative value of the function.
+    /// * `vars` - A Vec of String that labels the variables of the function. Must contain unique
+    ///            values.
+    /// * `dual` - A Vec of f64 that contains the first derivative information of the function.
+    ///            Must be same length as `vars` or empty.
+    /// * `dual2` - A Vec of f64, flattened row-major, that contains the second derivative
+    ///             (Hessian) information of the function. Must have length `vars.len()^2` or be
+    ///             empty.
+    ///
+    /// # Notes
+    ///
+    /// If `dual` is an empty vector it will be automatically set to a vector of 1.0's with the
+    /// same length as `vars`. If `dual2` is an empty vector it will be automatically set to a
+    /// zero matrix of shape `(vars.len(), vars.len())`.
+    #[new]
+    pub fn new(real: f64, vars: Vec<String>, dual: Vec<f64>, dual2: Vec<f64>) -> Self {
+        let new_dual;
+        if dual.len() != 0 && vars.len() != dual.len() {
+            panic!("`dual` must have same length as `vars` or have zero length.")
+        } else if dual.len() == 0 && vars.len() > 0 {
+            new_dual = Array::ones(vars.len());
+        } else {
+            new_dual = Array::from_vec(dual);
+        }
+
+        let n = vars.len();
+        let new_dual2;
+        if dual2.len() != 0 && dual2.len() != n * n {
+            panic!("`dual2` must have length equal to `vars.len()^2` or have zero length.")
+        } else if dual2.len() == 0 {
+            new_dual2 = Array::zeros((n, n));
+        } else {
+            new_dual2 = Array::from_shape_vec((n, n), dual2)
+                .expect("`dual2` could not be reshaped to (vars.len(), vars.len()).");
+        }
+
+        Self {
+            real: real,
+            vars: Arc::new(IndexSet::from_iter(vars)),
+            dual: new_dual,
+            dual2: new_dual2,
+        }
+    }
+
+    #[getter]
+    fn real(&self) -> PyResult<f64> {
+        Ok(self.real)
+    }
+
+    #[getter]
+    fn vars(&self) -> PyResult<Vec<&String>> {
+        Ok(Vec::from_iter(self.vars.iter()))
+    }
+
+    #[getter]
+    fn dual<'py>(&'py self, py: Python<'py>) -> PyResult<&PyArray1<f64>> {
+        Ok(self.dual.to_pyarray(py))
+    }
+
+    #[getter]
+    fn dual2<'py>(&'py self, py: Python<'py>) -> PyResult<&PyArray2<f64>> {
+        Ok(self.dual2.to_pyarray(py))
+    }
+
+    fn gradient<'py>(&'py self, py: Python<'py>, vars: Vec<String>) -> PyResult<&PyArray1<f64>> {
+        Ok(self.ggradient(vars).to_pyarray(py))
+    }
+
+    fn gradient2<'py>(&'py self, py: Python<'py>, vars: Vec<String>) -> PyResult<&PyArray2<f64>> {
+        Ok(self.ghessian(vars).to_pyarray(py))
+    }
+
+    fn arc_check(&self, other: &Dual2) -> PyResult<bool> {
+        Ok(Arc::ptr_eq(&self.vars, &other.vars))
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        let mut _vars = Vec::from_iter(self.vars.iter().take(3).map(String::as_str)).join(", ");
+        let mut _dual = Vec::from_iter(self.dual.iter().take(3).map(|x| x.to_string())).join(", ");
+        if self.vars.len() > 3 {
+            _vars.push_str(", ...");
+            _dual.push_str(", ...");
+        }
+        let fs = format!("<Dual2: {:.6}, ({}), [{}]>", self.real, _vars, _dual);
+        Ok(fs)
+    }
+
+    fn __eq__(&self, other:Dual2OrF64) -> PyResult<bool> {
+        match other {
+            Dual2OrF64::Dual2(d) => Ok(d.eq(self)),
+            Dual2OrF64::F64(f) => Ok(Dual2::new(f, Vec::new(), Vec::new(), Vec::new()).eq(self))
+        }
+    }
+
+    fn __lt__(&self, other:Dual2OrF64) -> PyResult<bool> {
+        match other {
+            Dual2OrF64::Dual2(d) => Ok(self < &d),
+            Dual2OrF64::F64(f) => Ok(self < &f)
+        }
+    }
+
+    fn __le__(&self, other:Dual2OrF64) -> PyResult<bool> {
+        match other {
+            Dual2OrF64::Dual2(d) => Ok(self <= &d),
+            Dual2OrF64::F64(f) => Ok(self <= &f)
+        }
+    }
+
+    fn __gt__(&self, other:Dual2OrF64) -> PyResult<bool> {
+        match other {
+            Dual2OrF64::Dual2(d) => Ok(self > &d),
+            Dual2OrF64::F64(f) => Ok(self > &f)
+        }
+    }
+
+    fn __ge__(&self, other:Dual2OrF64) -> PyResult<bool> {
+        match other {
+            Dual2OrF64::Dual2(d) => Ok(self >= &d),
+            Dual2OrF64::F64(f) => Ok(self >= &f)
+        }
+    }
+
+    fn __neg__(&self) -> Self {-self}
+
+    fn __add__(&self, other: Dual2OrF64) -> Self {
+        match other {
+            Dual2OrF64::Dual2(d) => self + d,
+            Dual2OrF64::F64(f) => self + f
+        }
+    }
+
+    fn __radd__(&self, other: Dual2OrF64) -> Self {
+        match other {
+            Dual2OrF64::Dual2(d) => self + d,
+            Dual2OrF64::F64(f) => self + f
+        }
+    }
+
+    fn __sub__(&self, other: Dual2OrF64) -> Self {
+        match other {
+            Dual2OrF64::Dual2(d) => self - d,
+            Dual2OrF64::F64(f) => self - f
+        }
+    }
+
+    fn __rsub__(&self, other: Dual2OrF64) -> Self {
+        match other {
+            Dual2OrF64::Dual2(d) => d - self,
+            Dual2OrF64::F64(f) => f - self
+        }
+    }
+
+    fn __mul__(&self, other: Dual2OrF64) -> Self {
+        match other {
+            Dual2OrF64::Dual2(d) => self * d,
+            Dual2OrF64::F64(f) => self * f
+        }
+    }
+
+    fn __rmul__(&self, other: Dual2OrF64) -> Self {
+        match other {
+            Dual2OrF64::Dual2(d) => d * self,
+            Dual2OrF64::F64(f) => f * self
+        }
+    }
+
+    fn __truediv__(&self, other: Dual2OrF64) -> Self {
+        match other {
+            Dual2OrF64::Dual2(d) => self / d,
+            Dual2OrF64::F64(f) => self / f
+        }
+    }
+
+    fn __rtruediv__(&self, other: Dual2OrF64) -> Self {
+        match other {
+            Dual2OrF64::Dual2(d) => d / self,
+            Dual2OrF64::F64(f) => f / self
+        }
+    }
+
+    fn __pow__(&self, power: f64, modulo: Option<i32>) -> Self {
+        if modulo.unwrap_or(0) != 0 {
+            panic!("Power function with mod not available for Dual2.")
+        }
+        self.clone().pow(power)
+    }
+
+    fn __exp__(&self) -> Self {
+        self.exp()
+    }
+
+    fn __abs__(&self) -> f64 {
+        self.abs()
+    }
+
+    fn __log__(&self) -> Self {
+        self.log()
+    }
+
+    fn __float__(&self) -> f64 {
+        self.real
+    }
+}
+
+impl Dual2 {
+    /// Return two equivalent Dual2 with same vars.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Alternative Dual2 against which vars comparison is made
+    fn to_combined_vars(&self, other: &Dual2) -> (Dual2, Dual2) {
+        if Arc::ptr_eq(&self.vars, &other.vars) {
+            (self.clone(), other.clone())
+        } else if self.vars.len() >= other.vars.len() && other.vars.iter().all(|var| self.vars.contains(var)) {
+            // vars in other are contained within self
+            (self.clone(), other.to_new_ordered_vars(&self.vars))
+        } else if self.vars.len() < other.vars.len() && self.vars.iter().all(|var| other.vars.contains(var)) {
+            // vars in self are contained within other
+            (self.to_new_ordered_vars(&other.vars), other.clone())
+        } else {
+            // vars in both self and other are different so recast
+            self.to_combined_vars_explicit(other)
+        }
+    }
+
+    /// Return two equivalent Dual2 with the unionised same, but explicitly recast, vars.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Alternative Dual2 against which vars comparison is made
+    fn to_combined_vars_explicit(&self, other: &Dual2) -> (Dual2, Dual2) {
+        let comb_vars = Arc::new(IndexSet::from_iter(self.vars.union(&other.vars).map(|x| x.clone())));
+        (self.to_new_vars(&comb_vars), other.to_new_vars(&comb_vars))
+    }
+
+    /// Return a Dual2 with recast vars if required.
+    pub fn to_new_ordered_vars(&self, new_vars: &Arc<IndexSet<String>>) -> Dual2 {
+        if self.vars.len() == new_vars.len() && self.vars.iter().zip(new_vars.iter()).all(|(a,b)| a==b) {
+            Dual2 {vars: Arc::clone(new_vars), real: self.real, dual: self.dual.clone(), dual2: self.dual2.clone()}
+        } else {
+            self.to_new_vars(new_vars)
+        }
+    }
+
+    fn to_new_vars(&self, new_vars: &Arc<IndexSet<String>>) -> Dual2 {
+        // Return a Dual2 with a new set of vars.
+        let mut dual = Array::zeros(new_vars.len());
+        let mut dual2 = Array::zeros((new_vars.len(), new_vars.len()));
+        let index_map: Vec<Option<usize>> = new_vars.iter().map(|x| self.vars.get_index_of(x)).collect();
+        for (i, index_i) in index_map.iter().enumerate() {
+            if let Some(vi) = index_i {
+                dual[[i]] = self.dual[[*vi]];
+                for (j, index_j) in index_map.iter().enumerate() {
+                    if let Some(vj) = index_j {
+                        dual2[[i, j]] = self.dual2[[*vi, *vj]];
+                    }
+                }
+            }
+        }
+        Dual2 {vars: Arc::clone(new_vars), real: self.real, dual, dual2}
+    }
+
+    fn ggradient(&self, vars: Vec<String>) -> Array1<f64> {
+        let mut dual = Array::zeros(vars.len());
+        for (i, index) in vars.iter().map(|x| self.vars.get_index_of(x)).enumerate() {
+            match index {
+                Some(value) => { dual[[i]] = self.dual[[value]] }
+                None => { dual[[i]] = 0.0 }
+            }
+        }
+        dual
+    }
+
+    fn ghessian(&self, vars: Vec<String>) -> Array2<f64> {
+        let indices: Vec<Option<usize>> = vars.iter().map(|x| self.vars.get_index_of(x)).collect();
+        let mut dual2 = Array::zeros((vars.len(), vars.len()));
+        for (i, index_i) in indices.iter().enumerate() {
+            if let Some(vi) = index_i {
+                for (j, index_j) in indices.iter().enumerate() {
+                    if let Some(vj) = index_j {
+                        dual2[[i, j]] = self.dual2[[*vi, *vj]];
+                    }
+                }
+            }
+        }
+        dual2
+    }
+
+    pub fn abs(&self) -> f64 {
+        self.real.abs()
+    }
+
+    pub fn exp(&self) -> Self {
+        let c = self.real.exp();
+        let grad = &self.dual;
+        Dual2 {
+            real: c,
+            vars: Arc::clone(&self.vars),
+            dual: c * grad,
+            dual2: c * outer(grad, grad) + c * &self.dual2,
+        }
+    }
+
+    pub fn log(&self) -> Self {
+        let grad = &self.dual;
+        Dual2 {
+            real: self.real.ln(),
+            vars: Arc::clone(&self.vars),
+            dual: (1.0 / self.real) * grad,
+            dual2: (-1.0 / self.real.powi(2)) * outer(grad, grad) + (1.0 / self.real) * &self.dual2,
+        }
+    }
+}
+
+/// Return the outer product of two first-derivative (gradient) vectors.
+fn outer(a: &Array1<f64>, b: &Array1<f64>) -> Array2<f64> {
+    let a2 = a.view().insert_axis(ndarray::Axis(1));
+    let b2 = b.view().insert_axis(ndarray::Axis(0));
+    a2.dot(&b2)
+}
+
+impl num_traits::identities::One for Dual2 {
+    fn one() -> Dual2 {
+        return Dual2::new(1.0, Vec::new(), Vec::new(), Vec::new())
+    }
+}
+
+impl num_traits::identities::Zero for Dual2 {
+    fn zero() -> Dual2 {
+        return Dual2::new(0.0, Vec::new(), Vec::new(), Vec::new())
+    }
+
+    fn is_zero(&self) -> bool {
+        return *self == Dual2::new(0.0, Vec::new(), Vec::new(), Vec::new())
+    }
+}
+
+impl num_traits::Pow<f64> for Dual2 {
+    type Output = Dual2;
+    fn pow(self, power: f64) -> Dual2 {
+        let f1 = power * self.real.powf(power - 1.0);
+        let f2 = power * (power - 1.0) * self.real.powf(power - 2.0);
+        return Dual2 {
+            real: self.real.powf(power),
+            dual2: f2 * outer(&self.dual, &self.dual) + f1 * &self.dual2,
+            dual: self.dual * f1,
+            vars: self.vars,
+        }
+    }
+}
+
+impl std::ops::AddAssign for Dual2 {
+    fn add_assign(&mut self, other: Self) {
+        let z = self.clone() + other;
+        self.vars = z.vars.clone();
+        self.dual = z.dual.clone();
+        self.dual2 = z.dual2.clone();
+        self.real = z.real;
+    }
+}
+
+impl std::ops::MulAssign for Dual2 {
+    fn mul_assign(&mut self, other: Self) {
+        let z = self.clone() * other;
+        self.vars = z.vars.clone();
+        self.dual = z.dual.clone();
+        self.dual2 = z.dual2.clone();
+        self.real = z.real;
+    }
+}
+
+impl_op!(- |a: Dual2| -> Dual2 { Dual2 {vars: a.vars, real: -a.real, dual: -a.dual, dual2: -a.dual2}});
+impl_op!(- |a: &Dual2| -> Dual2 { Dual2 {vars: a.vars.clone(), real: -a.real, dual: -(a.dual.clone()), dual2: -(a.dual2.clone())}});
+
+impl_op_ex_commutative!(+ |a: &Dual2, b: &f64| -> Dual2 { Dual2 {vars: Arc::clone(&a.vars), real: a.real + b, dual: a.dual.clone(), dual2: a.dual2.clone()} });
+impl_op_ex!(+ |a: &Dual2, b: &Dual2| -> Dual2 {
+    if Arc::ptr_eq(&a.vars, &b.vars) {
+        Dual2 {real: a.real + b.real, dual: &a.dual + &b.dual, dual2: &a.dual2 + &b.dual2, vars: Arc::clone(&a.vars)}
+    }
+    else {
+        let (x, y) = a.to_combined_vars(b);
+        x + y
+    }
+});
+
+impl_op_ex!(- |a: &Dual2, b: &f64| -> Dual2 { Dual2 {vars: Arc::clone(&a.vars), real: a.real - b, dual: a.dual.clone(), dual2: a.dual2.clone()} });
+impl_op_ex!(- |a: &f64, b: &Dual2| -> Dual2 { Dual2 {vars: Arc::clone(&b.vars), real: a - b.real, dual: -(b.dual.clone()), dual2: -(b.dual2.clone())} });
+impl_op_ex!(- |a: &Dual2, b: &Dual2| -> Dual2 {
+    if Arc::ptr_eq(&a.vars, &b.vars) {
+        Dual2 {real: a.real - b.real, dual: &a.dual - &b.dual, dual2: &a.dual2 - &b.dual2, vars: a.vars.clone()}
+    }
+    else {
+        let (x, y) = a.to_combined_vars(b);
+        x - y
+    }
+});
+
+impl_op_ex_commutative!(* |a: &Dual2, b: f64| -> Dual2 { Dual2 {vars: Arc::clone(&a.vars), real: a.real * b, dual: b * &a.dual, dual2: b * &a.dual2} });
+impl_op_ex!(* |a: &Dual2, b: &Dual2| -> Dual2 {
+    if Arc::ptr_eq(&a.vars, &b.vars) {
+        Dual2 {
+            real: a.real * b.real,
+            dual: &a.dual * b.real + &b.dual * a.real,
+            dual2: a.real * &b.dual2 + b.real * &a.dual2 + outer(&a.dual, &b.dual) + outer(&b.dual, &a.dual),
+            vars: a.vars.clone(),
+        }
+    }
+    else {
+        let (x, y) = a.to_combined_vars(b);
+        x * y
+    }
+});
+
+impl_op_ex!(/ |a: &Dual2, b: f64| -> Dual2 { Dual2 {vars: Arc::clone(&a.vars), real: a.real / b, dual: &a.dual / b, dual2: &a.dual2 / b} });
+impl_op_ex!(/ |a: f64, b: &Dual2| -> Dual2 { a * b.clone().pow(-1.0) });
+impl_op_ex!(/ |a: &Dual2, b: &Dual2| -> Dual2 { a * b.clone().pow(-1.0) });
+
+impl PartialEq<f64> for Dual2 {
+    fn eq(&self, other: &f64) -> bool {
+        return Dual2::new(*other, [].to_vec(), [].to_vec(), [].to_vec()) == *self;
+    }
+}
+
+impl PartialEq<Dual2> for f64 {
+    fn eq(&self, other: &Dual2) -> bool {
+        return Dual2::new(*self, [].to_vec(), [].to_vec(), [].to_vec()) == *other;
+    }
+}
+
+impl PartialEq<Dual2> for Dual2 {
+    fn eq(&self, other: &Dual2) -> bool {
+        if self.real != other.real {
+            false
+        } else if Arc::ptr_eq(&self.vars, &other.vars) {
+            self.dual.iter().eq(other.dual.iter()) && self.dual2.iter().eq(other.dual2.iter())
+        } else {
+            let (x, y) = self.to_combined_vars(other);
+            x.eq(&y)
+        }
+    }
+}
+
+impl PartialOrd<f64> for Dual2 {
+    fn partial_cmp(&self, other: &f64) -> Option<Ordering>{
+        if self.real == *other {
+            Some(Ordering::Equal)
+        } else if self.real < *other {
+            Some(Ordering::Less)
+        } else {
+            Some(Ordering::Greater)
+        }
+    }
+}
+
+impl PartialOrd<Dual2> for f64 {
+    fn partial_cmp(&self, other: &Dual2) -> Option<Ordering>{
+        if *self == other.real {
+            Some(Ordering::Equal)
+        } else if *self < other.real {
+            Some(Ordering::Less)
+        } else {
+            Some(Ordering::Greater)
+        }
+    }
+}
+
+impl PartialOrd<Dual2> for Dual2 {
+    fn partial_cmp(&self, other: &Dual2) -> Option<Ordering>{
+        if self.real == other.real {
+            Some(Ordering::Equal)
+        } else if self.real < other.real {
+            Some(Ordering::Less)
+        } else {
+            Some(Ordering::Greater)
+        }
+    }
+}
+
+impl std::iter::Sum for Dual2 {
+    fn sum<I>(iter: I) -> Self
+    where I: Iterator<Item = Dual2> {
+        return iter.fold(Dual2::new(0.0, [].to_vec(), [].to_vec(), [].to_vec()), |acc, x| acc + x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exp_propagates_gradient_and_hessian() {
+        let x = Dual2::new(1.0, vec!["x".to_string()], vec![], vec![]);
+        let z = x.exp();
+        let e = std::f64::consts::E;
+        assert!((z.real - e).abs() < 1e-12);
+        assert!((z.dual[[0]] - e).abs() < 1e-12);
+        assert!((z.dual2[[0, 0]] - e).abs() < 1e-12);
+    }
+
+    #[test]
+    fn mul_produces_cross_hessian_term() {
+        let x = Dual2::new(2.0, vec!["x".to_string()], vec![], vec![]);
+        let y = Dual2::new(3.0, vec!["y".to_string()], vec![], vec![]);
+        let z = x * y;
+
+        assert_eq!(z.real, 6.0);
+
+        let vars = vec!["x".to_string(), "y".to_string()];
+        let grad = z.ggradient(vars.clone());
+        assert_eq!(grad[[0]], 3.0); // d(xy)/dx = y
+        assert_eq!(grad[[1]], 2.0); // d(xy)/dy = x
+
+        let hess = z.ghessian(vars);
+        assert_eq!(hess[[0, 0]], 0.0);
+        assert_eq!(hess[[1, 1]], 0.0);
+        assert_eq!(hess[[0, 1]], 1.0); // d^2(xy)/dxdy = 1
+        assert_eq!(hess[[1, 0]], 1.0);
+    }
+}
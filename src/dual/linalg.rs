@@ -1,51 +1,209 @@
-use crate::dual::dual1::DualOrF64;
-use ndarray::{Array, Array2, Array1, Zip, Axis, s, ArrayView1};
-
-// pub fn dual_tensordot(a: &Array<Duals>, b:&Array<Duals>) {
-//     let a_shape = a.shape();
-//     let b_shape = b.shape();
-//     let i: u16; let j: u16;
-//     (i, j) = (a_shape[a_shape.len()-1], b_shape[0]);
-//     let mut sum;
-//     for i in 0..(a_shape[a_shape.len()-1) {
-//         for j in 0..b_shape[0] {
-//             let sum = 0;
-//
-//             sum = sum + a[]
-//         }
-//     }
-// }
-
-enum Pivoting {
-    OnCopy,
-    OnUnderlying,
-}
-
-fn argabsmax(a: ArrayView1<i32>) -> usize {
-    let a: (usize, i32) = a.iter().enumerate().fold((0, 0), |acc, (i, elem)| {
-        if elem.abs() > acc.1 { (i, elem.clone()) } else { acc }
+use crate::dual::dual1::Dual;
+use crate::dual::number::Number;
+use ndarray::{Array, Array2, ArrayD, Array1, Zip, Axis, IxDyn, s, ArrayView1};
+
+/// Contract `a` and `b` over the given axes, numpy-`tensordot`-style: `axes.0` lists the axes of
+/// `a` and `axes.1` the corresponding axes of `b` to sum-reduce over (their lengths must match
+/// pairwise). The contraction is performed by permuting the contracted axes to the boundary,
+/// flattening each side to a 2-D matrix, and reusing `arr1_dot` (which already var-aligns and
+/// sums `Dual` products) for every output element; the result's `vars` are therefore merged
+/// automatically through `Dual`'s own arithmetic. Generic over `Number` so the same contraction
+/// runs in plain-float mode for speed or carries `Dual`/`Dual2` sensitivities for risk.
+pub fn tensordot<T: Number>(a: &ArrayD<T>, b: &ArrayD<T>, axes: (Vec<usize>, Vec<usize>)) -> ArrayD<T> {
+    let (a_axes, b_axes) = axes;
+    assert_eq!(
+        a_axes.len(), b_axes.len(),
+        "`axes` must name the same number of dimensions on `a` and `b`."
+    );
+
+    let a_free: Vec<usize> = (0..a.ndim()).filter(|d| !a_axes.contains(d)).collect();
+    let b_free: Vec<usize> = (0..b.ndim()).filter(|d| !b_axes.contains(d)).collect();
+
+    let a_free_dims: Vec<usize> = a_free.iter().map(|&d| a.len_of(Axis(d))).collect();
+    let a_contract_dims: Vec<usize> = a_axes.iter().map(|&d| a.len_of(Axis(d))).collect();
+    let b_contract_dims: Vec<usize> = b_axes.iter().map(|&d| b.len_of(Axis(d))).collect();
+    let b_free_dims: Vec<usize> = b_free.iter().map(|&d| b.len_of(Axis(d))).collect();
+    assert_eq!(
+        a_contract_dims, b_contract_dims,
+        "contracted axes of `a` and `b` must have matching lengths."
+    );
+
+    let m: usize = a_free_dims.iter().product();
+    let k: usize = a_contract_dims.iter().product();
+    let n: usize = b_free_dims.iter().product();
+
+    let mut a_order = a_free.clone();
+    a_order.extend(a_axes.iter());
+    let a_mat: Array2<T> = a.clone().permuted_axes(a_order).as_standard_layout()
+        .to_owned().into_shape((m, k)).expect("contraction reshape of `a` failed");
+
+    let mut b_order = b_axes.clone();
+    b_order.extend(b_free.iter());
+    let b_mat: Array2<T> = b.clone().permuted_axes(b_order).as_standard_layout()
+        .to_owned().into_shape((k, n)).expect("contraction reshape of `b` failed");
+
+    let mut out: Array2<T> = Array::from_elem((m, n), T::zero());
+    for i in 0..m {
+        for j in 0..n {
+            out[[i, j]] = arr1_dot(a_mat.row(i).to_owned(), b_mat.column(j).to_owned());
+        }
+    }
+
+    let mut out_shape = a_free_dims;
+    out_shape.extend(b_free_dims);
+    out.into_shape(IxDyn(&out_shape)).expect("contraction output reshape failed")
+}
+
+fn argabsmax<T: Number>(a: ArrayView1<T>) -> usize {
+    let (idx, _) = a.iter().enumerate().fold((0_usize, 0.0_f64), |acc, (i, elem)| {
+        let eabs = elem.abs();
+        if eabs > acc.1 { (i, eabs) } else { acc }
     });
-    a.0
+    idx
 }
 
-pub fn pivot_matrix(A: &Array2<T>) -> (Array2<i32>, Array2<T>) {
-    // pivot square matrix
-    let n = A.len_of(Axis(0));
-    let mut P: Array2<i32> = Array::eye(n);
-    let mut Pa = A.to_owned();  // initialise PA and Original (or)
-    // let Or = A.to_owned();
+/// The LU factorisation of a square matrix, following the layout used by `num-dual`: `a` holds
+/// the combined `L` (unit lower triangle, implicit ones on the diagonal) and `U` (upper
+/// triangle) after elimination, `p` is the row permutation applied to reach it (`p[i]` is the
+/// original row now in position `i`), and `p_count` is the number of row swaps performed (used to
+/// recover the sign of the determinant). `p` is also how the permutation from partial pivoting is
+/// exposed to callers — there is no separate pivot-only entry point.
+pub struct LU<T: Number> {
+    pub a: Array2<T>,
+    pub p: Vec<usize>,
+    pub p_count: usize,
+}
+
+impl<T: Number> LU<T> {
+    /// Materialise the row-permutation matrix `P` such that `PA = LU`.
+    pub fn permutation_matrix(&self) -> Array2<T> {
+        let n = self.p.len();
+        Array::from_shape_fn((n, n), |(i, j)| if self.p[i] == j { T::one() } else { T::zero() })
+    }
+}
+
+/// Factorise `A` as `PA = LU` with partial pivoting, generic over any `Number` so the same
+/// elimination code runs in plain-float mode for speed or in Dual/Dual2 mode for risk.
+pub fn lu_decompose<T: Number>(a: &Array2<T>) -> LU<T> {
+    let n = a.len_of(Axis(0));
+    let mut mat: Array2<T> = a.to_owned();
+    let mut p: Vec<usize> = (0..n).collect();
+    let mut p_count = 0_usize;
+
     for j in 0..n {
-        let k = argabsmax(Pa.slice(s![j.., j])) + j;
+        let k = argabsmax(mat.slice(s![j.., j])) + j;
         if j != k {
-            // define row swaps j <-> k  (note that k > j by definition)
-            let (mut Pt, mut Pb) = P.slice_mut(s![.., ..]).split_at(Axis(0), k);
-            let (r1, r2) = (Pt.row_mut(j), Pb.row_mut(0));
+            let (mut top, mut bottom) = mat.view_mut().split_at(Axis(0), k);
+            let (r1, r2) = (top.row_mut(j), bottom.row_mut(0));
             Zip::from(r1).and(r2).apply(std::mem::swap);
+            p.swap(j, k);
+            p_count += 1;
+        }
 
-            let (mut Pt, mut Pb) = Pa.slice_mut(s![.., ..]).split_at(Axis(0), k);
-            let (r1, r2) = (Pt.row_mut(j), Pb.row_mut(0));
-            Zip::from(r1).and(r2).apply(std::mem::swap);
+        for i in (j + 1)..n {
+            let factor = mat[[i, j]].clone() / mat[[j, j]].clone();
+            for col_idx in j..n {
+                mat[[i, col_idx]] = mat[[i, col_idx]].clone() - mat[[j, col_idx]].clone() * factor.clone();
+            }
+            mat[[i, j]] = factor;
         }
     }
-    (P, Pa)
-}
\ No newline at end of file
+    LU { a: mat, p, p_count }
+}
+
+/// Solve `Ax = b` for `x` by LU-factorising `A` with partial pivoting, then performing forward
+/// substitution on `Ly = Pb` followed by back substitution on `Ux = y`. This is the core
+/// operation a curve bootstrapper / Newton solver relies on; it is generic over `Number` so it
+/// runs over plain `f64` or carries `Dual`/`Dual2` sensitivities depending on the caller's types.
+pub fn dsolve<T: Number>(a: &Array2<T>, b: &Array1<T>) -> Array1<T> {
+    let n = a.len_of(Axis(0));
+    let lu = lu_decompose(a);
+
+    let mut y: Array1<T> = Array::from_elem(n, T::zero());
+    for i in 0..n {
+        let mut acc = b[lu.p[i]].clone();
+        for j in 0..i {
+            acc = acc - lu.a[[i, j]].clone() * y[j].clone();
+        }
+        y[i] = acc;
+    }
+
+    let mut x: Array1<T> = Array::from_elem(n, T::zero());
+    for i in (0..n).rev() {
+        let mut acc = y[i].clone();
+        for j in (i + 1)..n {
+            acc = acc - lu.a[[i, j]].clone() * x[j].clone();
+        }
+        x[i] = acc / lu.a[[i, i]].clone();
+    }
+
+    x
+}
+
+/// Consume two one-dimensional arrays and produce a scalar value of their dot product, generic
+/// over `Number`.
+pub fn arr1_dot<T: Number>(a1: Array1<T>, a2: Array1<T>) -> T {
+    a1.into_iter().zip(a2.into_iter())
+        .map(|(x, y)| x * y)
+        .fold(T::zero(), |acc, x| acc + x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    #[test]
+    fn dsolve_plain_f64_matches_known_solution() {
+        // 2x +  y = 3
+        //  x + 3y = 5
+        let a = arr2(&[[2.0, 1.0], [1.0, 3.0]]);
+        let b = Array1::from(vec![3.0, 5.0]);
+        let x = dsolve(&a, &b);
+        assert!((x[0] - 0.8).abs() < 1e-10);
+        assert!((x[1] - 1.4).abs() < 1e-10);
+    }
+
+    #[test]
+    fn dsolve_dual_sensitivities_match_finite_difference() {
+        let eps = 1e-6;
+        let a = arr2(&[[2.0, 1.0], [1.0, 3.0]]);
+
+        let b0 = Array1::from(vec![3.0, 5.0]);
+        let x0 = dsolve(&a, &b0);
+
+        let b_pert = Array1::from(vec![3.0 + eps, 5.0]);
+        let x_pert = dsolve(&a, &b_pert);
+        let fd_dx0_db0 = (x_pert[0] - x0[0]) / eps;
+        let fd_dx1_db0 = (x_pert[1] - x0[1]) / eps;
+
+        let a_dual = a.mapv(|v| Dual::new(v, Vec::new(), Vec::new()));
+        let b_dual = Array1::from(vec![
+            Dual::new(3.0, vec!["b0".to_string()], vec![]),
+            Dual::new(5.0, Vec::new(), Vec::new()),
+        ]);
+        let x_dual = dsolve(&a_dual, &b_dual);
+
+        let idx0 = x_dual[0].vars.get_index_of("b0").expect("b0 missing from result vars");
+        let idx1 = x_dual[1].vars.get_index_of("b0").expect("b0 missing from result vars");
+        assert!((x_dual[0].dual[[idx0]] - fd_dx0_db0).abs() < 1e-4);
+        assert!((x_dual[1].dual[[idx1]] - fd_dx1_db0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn tensordot_matches_matrix_multiplication() {
+        let d = |v: f64| Dual::new(v, Vec::new(), Vec::new());
+        let a = Array::from_shape_vec(IxDyn(&[2, 2]), vec![d(1.0), d(2.0), d(3.0), d(4.0)]).unwrap();
+        let b = Array::from_shape_vec(IxDyn(&[2, 2]), vec![d(5.0), d(6.0), d(7.0), d(8.0)]).unwrap();
+
+        // contract a's columns (axis 1) against b's rows (axis 0), i.e. plain matmul
+        let c = tensordot(&a, &b, (vec![1], vec![0])).into_dimensionality::<ndarray::Ix2>().unwrap();
+
+        let expected = [[19.0, 22.0], [43.0, 50.0]];
+        for i in 0..2 {
+            for j in 0..2 {
+                assert_eq!(c[[i, j]].real, expected[i][j]);
+            }
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod dual1;
+pub mod dual2;
+pub mod linalg;
+pub mod number;
@@ -0,0 +1,51 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num_traits::{One, Zero};
+
+use crate::dual::dual1::Dual;
+use crate::dual::dual2::Dual2;
+
+/// A scalar abstraction that linear-algebra routines can be written against once and run in
+/// either plain-float mode (fast, no sensitivities) or Dual/Dual2 mode (slower, but exact
+/// first- and/or second-order risk), chosen purely by the caller's type parameter.
+///
+/// Routines such as `argabsmax`, `lu_decompose`, `dsolve`, `arr1_dot` and `tensordot` are
+/// generic over this trait rather than matching on the variants of the `DualOrF64` enum.
+pub trait Number:
+    Sized
+    + Clone
+    + One
+    + Zero
+    + Neg<Output = Self>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Add<f64, Output = Self>
+    + Sub<f64, Output = Self>
+    + Mul<f64, Output = Self>
+    + Div<f64, Output = Self>
+    + PartialOrd
+    + PartialOrd<f64>
+{
+    /// The absolute value of the real (non-sensitivity) part.
+    fn abs(&self) -> f64;
+}
+
+impl Number for f64 {
+    fn abs(&self) -> f64 {
+        f64::abs(*self)
+    }
+}
+
+impl Number for Dual {
+    fn abs(&self) -> f64 {
+        Dual::abs(self)
+    }
+}
+
+impl Number for Dual2 {
+    fn abs(&self) -> f64 {
+        Dual2::abs(self)
+    }
+}
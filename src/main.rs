@@ -1,9 +1,7 @@
 
 pub mod dual;
-use dual::dual1::{Dual, DualOrF64};
-use dual::linalg::pivot_matrix;
-use ndarray::{Array1, Array};
-use ndarray::{Array2, arr2, s};
+use dual::linalg::{lu_decompose, dsolve};
+use ndarray::{arr1, arr2};
 
 fn main() {
     // let d1 = Dual::new(
@@ -25,21 +23,17 @@ fn main() {
     //
     // let elapsed = now.elapsed();
     // println!("Elapsed: {:.2?}", elapsed / 100000);
-    let A: Array2<DualOrF64> = arr2(&[
-        [DualOrF64::F64(1.),DualOrF64::Dual(Dual::new(2.0, vec![], vec![]))],
-        [DualOrF64::F64(4.),DualOrF64::Dual(Dual::new(5.0, vec![], vec![]))],
-    ]);
-    let a: (usize, DualOrF64) = A.slice(s![.., 0]).iter().enumerate().fold((0, DualOrF64::F64(0.0)), |acc, (i, elem)| {
-        if elem.abs() > acc.1 { (i, elem.clone()) } else { acc }
-    });
-    // let a = [1, 2, 3, 4, 5];
-    // let b = a.into_iter().enumerate().fold((0, 0), |s, (i, j)| (s.0 + i, s.1 + i * j));
-    // println!("{:?}", b); // Prints 40
-
-    let (x, y) = pivot_matrix(&A);
 
-    println!("{:?}", A);
-    println!("{:?}", A.slice(s![.., 0]));
-    println!("{:?}", a);
+    // plain-float mode: same lu_decompose/dsolve code, no sensitivities, chosen by type alone
+    let a = arr2(&[
+        [1.0_f64, 2.0],
+        [4.0, 5.0],
+    ]);
+    let lu = lu_decompose(&a);
+    println!("{:?}", lu.permutation_matrix());
+    println!("{:?}", lu.a);
 
+    let b = arr1(&[1.0_f64, 2.0]);
+    let x = dsolve(&a, &b);
+    println!("{:?}", x);
 }